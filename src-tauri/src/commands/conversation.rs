@@ -5,12 +5,33 @@
 //! Story 3.8: Atomic Write Transactions
 //!
 //! Uses rusqlite for transaction support (tauri-plugin-sql lacks this).
-//! Schema is defined by Drizzle in TypeScript - we just insert/update.
+//! Schema is owned by the Rust-side migrations in `db::migrations`, applied
+//! at startup before any of these commands can run.
 
 use crate::db::DbState;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tauri::State;
 
+/// Sentinel `valid_to` value meaning "still the live version" (Story: bitemporal history).
+pub(crate) const VALID_TO_INFINITY: &str = "9999-12-31T23:59:59Z";
+
+/// Parse an RFC3339 timestamp and normalize it to UTC, returning
+/// `(utc_rfc3339, original_offset)`. `created_at`/`valid_from`/`valid_to`
+/// are always stored and compared as UTC `...Z` strings, so every
+/// caller-supplied timestamp - a message's `created_at`, `revise_message`'s
+/// `at`, analytics `from`/`to` bounds - must go through here before it's
+/// used in a query, or a lexical string comparison against a non-UTC offset
+/// silently gives the wrong answer.
+fn normalize_rfc3339_to_utc(value: &str) -> Result<(String, String), String> {
+    let parsed = chrono::DateTime::parse_from_rfc3339(value)
+        .map_err(|e| format!("Invalid timestamp: {}", e))?;
+
+    let utc = parsed.with_timezone(&chrono::Utc);
+    let offset = parsed.format("%:z").to_string();
+
+    Ok((utc.to_rfc3339_opts(chrono::SecondsFormat::Secs, true), offset))
+}
+
 // =============================================================================
 // Types (aligned with Drizzle schema in src/db/schema/messages.ts)
 // =============================================================================
@@ -74,77 +95,23 @@ impl MessageToSave {
     }
 
     /// Validate timestamp format (Issue #6)
-    /// Critical fix: Validate numeric ranges, not just structure
+    ///
+    /// Parses with `chrono::DateTime::parse_from_rfc3339` instead of
+    /// hand-rolled byte offsets, so genuinely invalid calendar dates
+    /// (Feb 31, month 13, ...) are rejected by chrono's own validation
+    /// rather than a range check that doesn't know how many days are in
+    /// each month.
     fn validate_timestamp(&self) -> Result<(), String> {
-        let ts = &self.created_at;
-        if ts.is_empty() {
+        if self.created_at.is_empty() {
             return Err("Timestamp cannot be empty".to_string());
         }
 
-        // Minimum length for ISO 8601: YYYY-MM-DDTHH:MM:SS (19 chars)
-        // With timezone: YYYY-MM-DDTHH:MM:SSZ (20 chars) or +HH:MM (25 chars)
-        if ts.len() < 19 {
-            return Err("Timestamp format invalid (too short)".to_string());
-        }
-
-        let bytes = ts.as_bytes();
-
-        // Check structural delimiters
-        if bytes[4] != b'-' || bytes[7] != b'-' || bytes[10] != b'T' || bytes[13] != b':' || bytes[16] != b':' {
-            return Err("Timestamp format invalid (expected ISO 8601)".to_string());
-        }
-
-        // Parse and validate numeric components
-        let year_str = std::str::from_utf8(&bytes[0..4])
-            .map_err(|_| "Invalid timestamp: year not valid UTF-8")?;
-        let month_str = std::str::from_utf8(&bytes[5..7])
-            .map_err(|_| "Invalid timestamp: month not valid UTF-8")?;
-        let day_str = std::str::from_utf8(&bytes[8..10])
-            .map_err(|_| "Invalid timestamp: day not valid UTF-8")?;
-        let hour_str = std::str::from_utf8(&bytes[11..13])
-            .map_err(|_| "Invalid timestamp: hour not valid UTF-8")?;
-        let min_str = std::str::from_utf8(&bytes[14..16])
-            .map_err(|_| "Invalid timestamp: minute not valid UTF-8")?;
-        let sec_str = std::str::from_utf8(&bytes[17..19])
-            .map_err(|_| "Invalid timestamp: second not valid UTF-8")?;
-
-        // Validate year is numeric (we don't restrict range - future dates are fine)
-        year_str.parse::<u32>()
-            .map_err(|_| "Invalid timestamp: year must be numeric")?;
-
-        // Validate month (1-12)
-        let month: u32 = month_str.parse()
-            .map_err(|_| "Invalid timestamp: month must be numeric")?;
-        if month < 1 || month > 12 {
-            return Err("Invalid timestamp: month must be 1-12".to_string());
-        }
-
-        // Validate day (1-31, simplified - doesn't check per-month)
-        let day: u32 = day_str.parse()
-            .map_err(|_| "Invalid timestamp: day must be numeric")?;
-        if day < 1 || day > 31 {
-            return Err("Invalid timestamp: day must be 1-31".to_string());
-        }
-
-        // Validate hour (0-23)
-        let hour: u32 = hour_str.parse()
-            .map_err(|_| "Invalid timestamp: hour must be numeric")?;
-        if hour > 23 {
-            return Err("Invalid timestamp: hour must be 0-23".to_string());
-        }
+        let parsed = chrono::DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| format!("Invalid timestamp: {}", e))?;
 
-        // Validate minute (0-59)
-        let minute: u32 = min_str.parse()
-            .map_err(|_| "Invalid timestamp: minute must be numeric")?;
-        if minute > 59 {
-            return Err("Invalid timestamp: minute must be 0-59".to_string());
-        }
-
-        // Validate second (0-59, note: leap seconds 60 are technically valid but rare)
-        let second: u32 = sec_str.parse()
-            .map_err(|_| "Invalid timestamp: second must be numeric")?;
-        if second > 59 {
-            return Err("Invalid timestamp: second must be 0-59".to_string());
+        // chrono accepts `:60` as a leap second; seconds are limited to 0-59 here.
+        if parsed.timestamp_subsec_nanos() >= 1_000_000_000 {
+            return Err("Invalid timestamp: second out of range".to_string());
         }
 
         Ok(())
@@ -156,6 +123,16 @@ impl MessageToSave {
         self.validate_timestamp()?;
         Ok(())
     }
+
+    /// Normalize `created_at` to UTC for storage while retaining the
+    /// original UTC offset, so reads can still render device-local
+    /// wall-clock time regardless of which device saved the turn.
+    ///
+    /// Must only be called after `validate()` has confirmed the timestamp
+    /// parses.
+    fn normalized_created_at(&self) -> Result<(String, String), String> {
+        normalize_rfc3339_to_utc(&self.created_at)
+    }
 }
 
 /// Conversation update payload
@@ -173,116 +150,162 @@ pub struct ConversationUpdate {
 // Commands
 // =============================================================================
 
-/// Save a conversation turn atomically (Story 3.8)
-///
-/// Saves both user and assistant messages in a single transaction,
-/// updating conversation metadata atomically.
+/// Builds and atomically persists a conversation turn of arbitrary length
 ///
-/// If ANY operation fails, the entire transaction is rolled back.
-#[tauri::command]
-pub fn save_conversation_turn(
-    db: State<'_, DbState>,
-    update: ConversationUpdate,
-) -> Result<(), String> {
-    // Validate input messages (Issue #5 and #6)
-    update.user_message.validate()?;
-    update.assistant_message.validate()?;
+/// Replaces the old hardcoded user+assistant pair so tool-call loops
+/// (assistant -> tool result -> assistant again), system prompts, and
+/// multi-message streaming turns can all be saved as one ordered batch.
+pub struct ConversationTurnBuilder {
+    conversation_id: String,
+    session_id: Option<String>,
+    messages: Vec<MessageToSave>,
+}
 
-    // Sanitize error messages - don't leak database internals to frontend
-    let mut conn = db
-        .conn
-        .lock()
-        .map_err(|_| "Database temporarily unavailable".to_string())?;
+impl ConversationTurnBuilder {
+    pub fn new(conversation_id: impl Into<String>) -> Self {
+        Self {
+            conversation_id: conversation_id.into(),
+            session_id: None,
+            messages: Vec::new(),
+        }
+    }
 
-    // Run all operations in a single transaction
-    let tx = conn
-        .transaction()
-        .map_err(|_| "Failed to start database operation".to_string())?;
+    pub fn session_id(mut self, session_id: Option<String>) -> Self {
+        self.session_id = session_id;
+        self
+    }
 
-    // 1. Ensure conversation exists (or create)
-    // Schema: id, title, sdk_session_id, type, project_id, started_at, last_message_at, message_count, context_summary
-    let conv_exists: bool = tx
-        .query_row(
-            "SELECT 1 FROM conversations WHERE id = ?",
-            [&update.conversation_id],
-            |_| Ok(true),
-        )
-        .unwrap_or(false);
+    pub fn message(mut self, message: MessageToSave) -> Self {
+        self.messages.push(message);
+        self
+    }
+
+    pub fn messages(mut self, messages: Vec<MessageToSave>) -> Self {
+        self.messages.extend(messages);
+        self
+    }
+
+    /// Validate every message, then insert them all (and update conversation
+    /// metadata) in a single transaction. If ANY operation fails, the
+    /// entire transaction is rolled back.
+    pub fn save(self, db: &DbState) -> Result<(), String> {
+        if self.messages.is_empty() {
+            return Err("A conversation turn must have at least one message".to_string());
+        }
 
-    if !conv_exists {
+        let mut normalized = Vec::with_capacity(self.messages.len());
+        for message in &self.messages {
+            message.validate()?;
+            let (created_at, offset) = message.normalized_created_at()?;
+            normalized.push((created_at, offset));
+        }
+
+        let mut conn = db
+            .pool
+            .get()
+            .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|_| "Failed to start database operation".to_string())?;
+
+        // 1. Ensure conversation exists (or create)
+        let conv_exists: bool = tx
+            .query_row(
+                "SELECT 1 FROM conversations WHERE id = ?",
+                [&self.conversation_id],
+                |_| Ok(true),
+            )
+            .unwrap_or(false);
+
+        if !conv_exists {
+            tx.execute(
+                r#"
+                INSERT INTO conversations (id, sdk_session_id, type, started_at, last_message_at, message_count)
+                VALUES (?1, ?2, 'adhoc', ?3, ?3, 0)
+                "#,
+                rusqlite::params![
+                    &self.conversation_id,
+                    &self.session_id.clone().unwrap_or_default(),
+                    &normalized[0].0,
+                ],
+            )
+            .map_err(|_| "Failed to create conversation".to_string())?;
+        }
+
+        // 2. Insert each message in order
+        // Schema: id, conversation_id, role, content, tool_calls, tool_results, created_at,
+        // created_at_offset, message_id, valid_from, valid_to (live rows default valid_to to "infinity")
+        let mut last_message_at = normalized[0].0.clone();
+        for (message, (created_at, offset)) in self.messages.iter().zip(normalized.iter()) {
+            tx.execute(
+                r#"
+                INSERT INTO messages (id, conversation_id, role, content, tool_calls, tool_results, created_at, created_at_offset, message_id, valid_from, valid_to)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?1, ?7, ?9)
+                "#,
+                rusqlite::params![
+                    &message.id,
+                    &self.conversation_id,
+                    &message.role,
+                    &message.content,
+                    &message.tool_calls,
+                    &message.tool_results,
+                    created_at,
+                    offset,
+                    VALID_TO_INFINITY,
+                ],
+            )
+            .map_err(|_| format!("Failed to save message {}", message.id))?;
+
+            if created_at > &last_message_at {
+                last_message_at = created_at.clone();
+            }
+        }
+
+        // 3. Update conversation metadata
         tx.execute(
             r#"
-            INSERT INTO conversations (id, sdk_session_id, type, started_at, last_message_at, message_count)
-            VALUES (?1, ?2, 'adhoc', ?3, ?3, 0)
+            UPDATE conversations
+            SET last_message_at = ?2,
+                message_count = message_count + ?3
+            WHERE id = ?1
             "#,
             rusqlite::params![
-                &update.conversation_id,
-                &update.session_id.clone().unwrap_or_default(),
-                &update.user_message.created_at,
+                &self.conversation_id,
+                &last_message_at,
+                self.messages.len() as i64
             ],
         )
-        .map_err(|_| "Failed to create conversation".to_string())?;
-    }
-
-    // 2. Insert user message
-    // Schema: id, conversation_id, role, content, tool_calls, tool_results, created_at
-    tx.execute(
-        r#"
-        INSERT INTO messages (id, conversation_id, role, content, tool_calls, tool_results, created_at)
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
-        "#,
-        rusqlite::params![
-            &update.user_message.id,
-            &update.conversation_id,
-            &update.user_message.role,
-            &update.user_message.content,
-            &update.user_message.tool_calls,
-            &update.user_message.tool_results,
-            &update.user_message.created_at,
-        ],
-    )
-    .map_err(|_| "Failed to save user message".to_string())?;
+        .map_err(|_| "Failed to update conversation".to_string())?;
 
-    // 3. Insert assistant message
-    tx.execute(
-        r#"
-        INSERT INTO messages (id, conversation_id, role, content, tool_calls, tool_results, created_at)
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
-        "#,
-        rusqlite::params![
-            &update.assistant_message.id,
-            &update.conversation_id,
-            &update.assistant_message.role,
-            &update.assistant_message.content,
-            &update.assistant_message.tool_calls,
-            &update.assistant_message.tool_results,
-            &update.assistant_message.created_at,
-        ],
-    )
-    .map_err(|_| "Failed to save assistant message".to_string())?;
+        // Commit transaction - if this fails, all changes are rolled back
+        tx.commit()
+            .map_err(|_| "Failed to save conversation".to_string())?;
 
-    // 4. Update conversation metadata
-    tx.execute(
-        r#"
-        UPDATE conversations
-        SET last_message_at = ?2,
-            message_count = message_count + 2
-        WHERE id = ?1
-        "#,
-        rusqlite::params![&update.conversation_id, &update.assistant_message.created_at],
-    )
-    .map_err(|_| "Failed to update conversation".to_string())?;
-
-    // Commit transaction - if this fails, all changes are rolled back
-    tx.commit()
-        .map_err(|_| "Failed to save conversation".to_string())?;
+        println!(
+            "[conversation] Saved turn to {} ({} messages)",
+            self.conversation_id,
+            self.messages.len()
+        );
 
-    println!(
-        "[conversation] Saved turn to {} (2 messages)",
-        update.conversation_id
-    );
+        Ok(())
+    }
+}
 
-    Ok(())
+/// Save a conversation turn atomically (Story 3.8)
+///
+/// Thin wrapper around `ConversationTurnBuilder` for the common two-message
+/// case, kept so existing callers don't break.
+#[tauri::command]
+pub fn save_conversation_turn(
+    db: State<'_, DbState>,
+    update: ConversationUpdate,
+) -> Result<(), String> {
+    ConversationTurnBuilder::new(update.conversation_id)
+        .session_id(update.session_id)
+        .message(update.user_message)
+        .message(update.assistant_message)
+        .save(&db)
 }
 
 /// Get or create a conversation based on session type
@@ -294,8 +317,8 @@ pub fn get_or_create_conversation(
     project_id: Option<String>,
 ) -> Result<String, String> {
     let conn = db
-        .conn
-        .lock()
+        .pool
+        .get()
         .map_err(|_| "Database temporarily unavailable".to_string())?;
 
     // Generate conversation ID based on session type
@@ -338,12 +361,727 @@ pub fn get_or_create_conversation(
 
         println!("[conversation] Created new conversation: {}", conv_id);
     } else {
-        println!("[conversation] Using existing conversation: {}", conv_id);
+        let tz = timezone_of(&conn, &conv_id).unwrap_or_else(|_| "UTC".to_string());
+        println!(
+            "[conversation] Using existing conversation: {} (tz: {})",
+            conv_id, tz
+        );
     }
 
     Ok(conv_id)
 }
 
+/// Look up the IANA timezone stored for a conversation, defaulting to UTC
+///
+/// Lazily reads `conversations.timezone` rather than caching it, mirroring
+/// how per-user timezone lookups are done elsewhere - conversations are
+/// read far less often than they're written, so there's no reuse benefit
+/// to caching the value in app state.
+fn timezone_of(conn: &rusqlite::Connection, conversation_id: &str) -> Result<String, String> {
+    conn.query_row(
+        "SELECT timezone FROM conversations WHERE id = ?",
+        [conversation_id],
+        |row| row.get(0),
+    )
+    .or_else(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => Ok("UTC".to_string()),
+        _ => Err(format!("Failed to read conversation timezone: {}", e)),
+    })
+}
+
+/// Set the IANA timezone used to render times for a conversation
+#[tauri::command]
+pub fn set_conversation_timezone(
+    db: State<'_, DbState>,
+    conversation_id: String,
+    timezone: String,
+) -> Result<(), String> {
+    timezone
+        .parse::<chrono_tz::Tz>()
+        .map_err(|_| format!("Unknown IANA timezone: {}", timezone))?;
+
+    let conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let updated = conn
+        .execute(
+            "UPDATE conversations SET timezone = ?1 WHERE id = ?2",
+            rusqlite::params![&timezone, &conversation_id],
+        )
+        .map_err(|_| "Failed to update conversation timezone".to_string())?;
+
+    if updated == 0 {
+        return Err(format!("Conversation not found: {}", conversation_id));
+    }
+
+    Ok(())
+}
+
+/// One ranked full-text search hit
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageSearchHit {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub created_at: String,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+/// Full-text search over saved message content
+///
+/// Backed by the `messages_fts` FTS5 shadow table. Supports the full FTS5
+/// query syntax (phrases, `term*` prefix queries, ...), ranks hits by
+/// `bm25()`, and returns a highlighted snippet via `snippet()`. Only
+/// searches the currently-live version of each message.
+#[tauri::command]
+pub fn search_messages(
+    db: State<'_, DbState>,
+    query: String,
+    conversation_id: Option<String>,
+    limit: i32,
+) -> Result<Vec<MessageSearchHit>, String> {
+    let conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let mut sql = String::from(
+        r#"
+        SELECT
+            m.message_id,
+            m.conversation_id,
+            m.created_at,
+            snippet(messages_fts, 0, '<mark>', '</mark>', '…', 10) AS snippet,
+            bm25(messages_fts) AS rank
+        FROM messages_fts
+        JOIN messages m ON m.rowid = messages_fts.rowid
+        WHERE messages_fts MATCH ? AND m.valid_to = ?
+        "#,
+    );
+
+    let infinity = VALID_TO_INFINITY.to_string();
+    let mut params: Vec<&dyn rusqlite::ToSql> = vec![&query, &infinity];
+
+    if let Some(ref cid) = conversation_id {
+        sql.push_str(" AND m.conversation_id = ?");
+        params.push(cid);
+    }
+    sql.push_str(" ORDER BY rank LIMIT ?");
+    params.push(&limit);
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Search query preparation failed: {}", e))?;
+
+    let hits = stmt
+        .query_map(params.as_slice(), |row| {
+            Ok(MessageSearchHit {
+                message_id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                created_at: row.get(2)?,
+                snippet: row.get(3)?,
+                rank: row.get(4)?,
+            })
+        })
+        .map_err(|_| "Search failed".to_string())?
+        .filter_map(|r| match r {
+            Ok(hit) => Some(hit),
+            Err(e) => {
+                eprintln!("[conversation] Row parse error in search_messages: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    Ok(hits)
+}
+
+/// A message as seen at a particular point in conversation history
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalMessage {
+    pub message_id: String,
+    pub role: String,
+    pub content: String,
+    pub tool_calls: Option<String>,
+    pub tool_results: Option<String>,
+    pub created_at: String,
+    pub valid_from: String,
+    pub valid_to: String,
+}
+
+/// Revise a previously-saved message without losing its history
+///
+/// `at` is validated as RFC3339 and normalized to UTC, then used to close
+/// the currently-live row for `message_id` before inserting a new physical
+/// row holding `new_content` as the live version from `at` onward. The
+/// previous content remains intact (and queryable via
+/// `get_conversation_as_of`) rather than being overwritten.
+#[tauri::command]
+pub fn revise_message(
+    db: State<'_, DbState>,
+    conversation_id: String,
+    message_id: String,
+    new_content: String,
+    at: String,
+) -> Result<(), String> {
+    let (at, _) = normalize_rfc3339_to_utc(&at)?;
+
+    let mut conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let tx = conn
+        .transaction()
+        .map_err(|_| "Failed to start database operation".to_string())?;
+
+    // Close the currently-live interval for this logical message.
+    let closed = tx
+        .execute(
+            r#"
+            UPDATE messages
+            SET valid_to = ?1
+            WHERE conversation_id = ?2 AND message_id = ?3 AND valid_to = ?4
+            "#,
+            rusqlite::params![&at, &conversation_id, &message_id, VALID_TO_INFINITY],
+        )
+        .map_err(|_| "Failed to close previous message version".to_string())?;
+
+    if closed == 0 {
+        return Err(format!(
+            "No live message found for {} in {}",
+            message_id, conversation_id
+        ));
+    }
+
+    let (role, tool_calls, tool_results, created_at, created_at_offset): (
+        String,
+        Option<String>,
+        Option<String>,
+        String,
+        String,
+    ) = tx
+        .query_row(
+            r#"
+            SELECT role, tool_calls, tool_results, created_at, created_at_offset
+            FROM messages
+            WHERE conversation_id = ?1 AND message_id = ?2 AND valid_to = ?3
+            "#,
+            rusqlite::params![&conversation_id, &message_id, &at],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )
+        .map_err(|_| "Failed to read previous message version".to_string())?;
+
+    // New physical row for the same logical message; `id` must stay unique
+    // per row, so the logical grouping lives in `message_id` instead. The
+    // original `created_at`/`created_at_offset` carry forward unchanged -
+    // only `valid_from` moves to the revision time - so a revised message
+    // stays in its original chronological position instead of jumping to
+    // its edit timestamp.
+    let row_id = format!("{}rev{}", message_id, uuid::Uuid::new_v4().simple());
+
+    tx.execute(
+        r#"
+        INSERT INTO messages (id, conversation_id, message_id, role, content, tool_calls, tool_results, created_at, created_at_offset, valid_from, valid_to)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+        "#,
+        rusqlite::params![
+            &row_id,
+            &conversation_id,
+            &message_id,
+            &role,
+            &new_content,
+            &tool_calls,
+            &tool_results,
+            &created_at,
+            &created_at_offset,
+            &at,
+            VALID_TO_INFINITY,
+        ],
+    )
+    .map_err(|_| "Failed to insert revised message".to_string())?;
+
+    tx.commit()
+        .map_err(|_| "Failed to save message revision".to_string())?;
+
+    Ok(())
+}
+
+/// Reconstruct a conversation's transcript as it looked at a given instant
+///
+/// For each logical `message_id`, selects the row whose validity interval
+/// contained `at` (`valid_from <= at < valid_to`), so edited/regenerated
+/// turns can be "rewound" without losing the audit trail.
+#[tauri::command]
+pub fn get_conversation_as_of(
+    db: State<'_, DbState>,
+    conversation_id: String,
+    at: String,
+) -> Result<Vec<HistoricalMessage>, String> {
+    let (at, _) = normalize_rfc3339_to_utc(&at)?;
+
+    let conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT message_id, role, content, tool_calls, tool_results, created_at, valid_from, valid_to
+            FROM messages
+            WHERE conversation_id = ?1 AND valid_from <= ?2 AND ?2 < valid_to
+            ORDER BY created_at ASC
+            "#,
+        )
+        .map_err(|e| format!("Query preparation failed: {}", e))?;
+
+    let messages = stmt
+        .query_map(rusqlite::params![&conversation_id, &at], |row| {
+            Ok(HistoricalMessage {
+                message_id: row.get(0)?,
+                role: row.get(1)?,
+                content: row.get(2)?,
+                tool_calls: row.get(3)?,
+                tool_results: row.get(4)?,
+                created_at: row.get(5)?,
+                valid_from: row.get(6)?,
+                valid_to: row.get(7)?,
+            })
+        })
+        .map_err(|e| format!("Query failed: {}", e))?
+        .filter_map(|r| match r {
+            Ok(msg) => Some(msg),
+            Err(e) => {
+                eprintln!("[conversation] Row parse error in get_conversation_as_of: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    Ok(messages)
+}
+
+/// Current version of the exported transcript document format.
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// One message row within an exported transcript, including the
+/// bitemporal/timezone metadata needed to reconstruct its full history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationExportMessage {
+    pub id: String,
+    pub message_id: String,
+    pub role: String,
+    pub content: String,
+    pub tool_calls: Option<String>,
+    pub tool_results: Option<String>,
+    pub created_at: String,
+    pub created_at_offset: String,
+    pub valid_from: String,
+    pub valid_to: String,
+}
+
+/// A self-contained, portable transcript: enough to recreate a
+/// conversation (including its revision history) in another database
+/// without depending on any row ids already present there.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationExport {
+    pub format_version: u32,
+    pub conversation_id: String,
+    pub session_type: String,
+    pub project_id: Option<String>,
+    pub timezone: String,
+    pub started_at: String,
+    pub last_message_at: String,
+    pub messages: Vec<ConversationExportMessage>,
+}
+
+/// Export a conversation (every message row, including revision history)
+/// as a self-contained JSON transcript.
+///
+/// Unlike `get_conversation_as_of`, this includes every physical row for
+/// every `message_id`, not just whichever was live at one instant, so
+/// importing the document elsewhere reproduces the full edit history.
+#[tauri::command]
+pub fn export_conversation(
+    db: State<'_, DbState>,
+    conversation_id: String,
+) -> Result<String, String> {
+    let conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let (session_type, project_id, timezone, started_at, last_message_at) = conn
+        .query_row(
+            r#"
+            SELECT type, project_id, timezone, started_at, last_message_at
+            FROM conversations
+            WHERE id = ?
+            "#,
+            [&conversation_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            },
+        )
+        .map_err(|_| format!("Conversation not found: {}", conversation_id))?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, message_id, role, content, tool_calls, tool_results, created_at, created_at_offset, valid_from, valid_to
+            FROM messages
+            WHERE conversation_id = ?1
+            ORDER BY valid_from ASC, id ASC
+            "#,
+        )
+        .map_err(|e| format!("Query preparation failed: {}", e))?;
+
+    let messages = stmt
+        .query_map([&conversation_id], |row| {
+            Ok(ConversationExportMessage {
+                id: row.get(0)?,
+                message_id: row.get(1)?,
+                role: row.get(2)?,
+                content: row.get(3)?,
+                tool_calls: row.get(4)?,
+                tool_results: row.get(5)?,
+                created_at: row.get(6)?,
+                created_at_offset: row.get(7)?,
+                valid_from: row.get(8)?,
+                valid_to: row.get(9)?,
+            })
+        })
+        .map_err(|e| format!("Query failed: {}", e))?
+        .filter_map(|r| match r {
+            Ok(msg) => Some(msg),
+            Err(e) => {
+                eprintln!("[conversation] Row parse error in export_conversation: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    let export = ConversationExport {
+        format_version: EXPORT_FORMAT_VERSION,
+        conversation_id,
+        session_type,
+        project_id,
+        timezone,
+        started_at,
+        last_message_at,
+        messages,
+    };
+
+    serde_json::to_string_pretty(&export).map_err(|e| format!("Failed to encode transcript: {}", e))
+}
+
+/// Import a transcript previously produced by `export_conversation`
+///
+/// Re-validates every message the same way a fresh save would (Issue #5/#6
+/// rules still apply to imported data), and remaps the conversation id if
+/// one with the same id already exists so a transcript can be imported
+/// alongside its origin without colliding. Returns the id the conversation
+/// was actually stored under. All rows are inserted in one transaction.
+#[tauri::command]
+pub fn import_conversation(db: State<'_, DbState>, document: String) -> Result<String, String> {
+    let export: ConversationExport =
+        serde_json::from_str(&document).map_err(|e| format!("Invalid transcript: {}", e))?;
+
+    if export.format_version != EXPORT_FORMAT_VERSION {
+        return Err(format!(
+            "Unsupported transcript format version: {}",
+            export.format_version
+        ));
+    }
+    if export.messages.is_empty() {
+        return Err("Transcript has no messages to import".to_string());
+    }
+
+    for message in &export.messages {
+        MessageToSave {
+            id: message.id.clone(),
+            role: message.role.clone(),
+            content: message.content.clone(),
+            tool_calls: message.tool_calls.clone(),
+            tool_results: message.tool_results.clone(),
+            created_at: message.created_at.clone(),
+        }
+        .validate()?;
+    }
+
+    let mut conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let tx = conn
+        .transaction()
+        .map_err(|_| "Failed to start database operation".to_string())?;
+
+    let conv_exists: bool = tx
+        .query_row(
+            "SELECT 1 FROM conversations WHERE id = ?",
+            [&export.conversation_id],
+            |_| Ok(true),
+        )
+        .unwrap_or(false);
+
+    let conversation_id = if conv_exists {
+        format!("{}_import_{}", export.conversation_id, uuid::Uuid::new_v4().simple())
+    } else {
+        export.conversation_id.clone()
+    };
+
+    // When the conversation id was remapped, the message rows' own ids (the
+    // `messages` table PRIMARY KEY) must be remapped too, or re-importing a
+    // transcript - or importing one alongside its origin, the documented use
+    // case - hits a UNIQUE violation. `message_id` is the bitemporal grouping
+    // column shared by a message's closed and live revision rows, so every
+    // row carrying the same original `message_id` must map to the same new
+    // one; this map makes that consistent across the loop below.
+    let remap_ids = conv_exists;
+    let mut message_id_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+
+    // `export.messages` includes every physical row (closed revisions and
+    // live rows alike), but `message_count` elsewhere only ever counts live
+    // logical messages - `revise_message` never increments it. Count only
+    // the live rows here so the imported conversation's count agrees with
+    // what `load_session`/`get_conversation_as_of` actually return.
+    let live_message_count = export
+        .messages
+        .iter()
+        .filter(|m| m.valid_to == VALID_TO_INFINITY)
+        .count() as i64;
+
+    tx.execute(
+        r#"
+        INSERT INTO conversations (id, sdk_session_id, type, project_id, timezone, started_at, last_message_at, message_count)
+        VALUES (?1, '', ?2, ?3, ?4, ?5, ?6, ?7)
+        "#,
+        rusqlite::params![
+            &conversation_id,
+            &export.session_type,
+            &export.project_id,
+            &export.timezone,
+            &export.started_at,
+            &export.last_message_at,
+            live_message_count,
+        ],
+    )
+    .map_err(|_| "Failed to create imported conversation".to_string())?;
+
+    for message in &export.messages {
+        let row_id = if remap_ids {
+            format!("{}_import_{}", message.id, uuid::Uuid::new_v4().simple())
+        } else {
+            message.id.clone()
+        };
+        let message_id = if remap_ids {
+            message_id_map
+                .entry(message.message_id.clone())
+                .or_insert_with(|| {
+                    format!("{}_import_{}", message.message_id, uuid::Uuid::new_v4().simple())
+                })
+                .clone()
+        } else {
+            message.message_id.clone()
+        };
+
+        tx.execute(
+            r#"
+            INSERT INTO messages (id, conversation_id, message_id, role, content, tool_calls, tool_results, created_at, created_at_offset, valid_from, valid_to)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            "#,
+            rusqlite::params![
+                &row_id,
+                &conversation_id,
+                &message_id,
+                &message.role,
+                &message.content,
+                &message.tool_calls,
+                &message.tool_results,
+                &message.created_at,
+                &message.created_at_offset,
+                &message.valid_from,
+                &message.valid_to,
+            ],
+        )
+        .map_err(|_| format!("Failed to import message {}", message.id))?;
+    }
+
+    tx.commit()
+        .map_err(|_| "Failed to save imported conversation".to_string())?;
+
+    println!(
+        "[conversation] Imported transcript as {} ({} messages)",
+        conversation_id,
+        export.messages.len()
+    );
+
+    Ok(conversation_id)
+}
+
+/// Message count for one day bucket (`YYYY-MM-DD`, UTC)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayBucketCount {
+    pub day: String,
+    pub count: i64,
+}
+
+/// Message count for one `role`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleCount {
+    pub role: String,
+    pub count: i64,
+}
+
+/// Aggregate usage stats for a date window, computed in SQL so the
+/// frontend never has to pull every matching row across the IPC boundary
+/// just to draw a chart.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationAnalytics {
+    pub messages_per_day: Vec<DayBucketCount>,
+    pub messages_by_role: Vec<RoleCount>,
+    pub active_conversations: i64,
+    pub avg_messages_per_conversation: f64,
+}
+
+/// Validate an analytics date bound and normalize it to UTC, using the
+/// same RFC3339 rules as message timestamps (Issue #6). `m.created_at` is
+/// always stored UTC-normalized, so a bound compared against it lexically
+/// must be normalized the same way first, or an offset form like
+/// `+05:30` compares incorrectly against the stored `...Z` values.
+fn validate_bound(label: &str, value: &str) -> Result<String, String> {
+    if value.is_empty() {
+        return Err(format!("{} cannot be empty", label));
+    }
+    let (utc, _) = normalize_rfc3339_to_utc(value).map_err(|e| format!("Invalid {}: {}", label, e))?;
+    Ok(utc)
+}
+
+/// Conversation/message aggregates over a date window
+///
+/// Only considers the currently-live version of each message (see
+/// `VALID_TO_INFINITY`), optionally narrowed to one session type and/or
+/// role. Buckets, grouping, and counting all happen in SQL.
+#[tauri::command]
+pub fn conversation_analytics(
+    db: State<'_, DbState>,
+    from: String,
+    to: String,
+    session_type: Option<String>,
+    role: Option<String>,
+) -> Result<ConversationAnalytics, String> {
+    let from = validate_bound("from", &from)?;
+    let to = validate_bound("to", &to)?;
+
+    let conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let infinity = VALID_TO_INFINITY.to_string();
+
+    // Shared WHERE clause for all four aggregates below.
+    let mut where_clause = String::from(
+        "m.valid_to = ?1 AND m.created_at >= ?2 AND m.created_at <= ?3",
+    );
+    let mut params: Vec<&dyn rusqlite::ToSql> = vec![&infinity, &from, &to];
+
+    if let Some(ref t) = session_type {
+        where_clause.push_str(" AND c.type = ?");
+        params.push(t);
+    }
+    if let Some(ref r) = role {
+        where_clause.push_str(" AND m.role = ?");
+        params.push(r);
+    }
+
+    let base_from = "FROM messages m JOIN conversations c ON c.id = m.conversation_id";
+
+    let mut per_day_stmt = conn
+        .prepare(&format!(
+            "SELECT substr(m.created_at, 1, 10) AS day, COUNT(*) \
+             {base_from} WHERE {where_clause} GROUP BY day ORDER BY day ASC",
+        ))
+        .map_err(|e| format!("Query preparation failed: {}", e))?;
+
+    let messages_per_day = per_day_stmt
+        .query_map(params.as_slice(), |row| {
+            Ok(DayBucketCount {
+                day: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })
+        .map_err(|e| format!("Query failed: {}", e))?
+        .filter_map(Result::ok)
+        .collect();
+
+    let mut by_role_stmt = conn
+        .prepare(&format!(
+            "SELECT m.role, COUNT(*) {base_from} WHERE {where_clause} GROUP BY m.role ORDER BY m.role ASC",
+        ))
+        .map_err(|e| format!("Query preparation failed: {}", e))?;
+
+    let messages_by_role = by_role_stmt
+        .query_map(params.as_slice(), |row| {
+            Ok(RoleCount {
+                role: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })
+        .map_err(|e| format!("Query failed: {}", e))?
+        .filter_map(Result::ok)
+        .collect();
+
+    let active_conversations: i64 = conn
+        .query_row(
+            &format!(
+                "SELECT COUNT(DISTINCT m.conversation_id) {base_from} WHERE {where_clause}",
+            ),
+            params.as_slice(),
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Query failed: {}", e))?;
+
+    let total_messages: i64 = conn
+        .query_row(
+            &format!("SELECT COUNT(*) {base_from} WHERE {where_clause}"),
+            params.as_slice(),
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Query failed: {}", e))?;
+
+    let avg_messages_per_conversation = if active_conversations > 0 {
+        total_messages as f64 / active_conversations as f64
+    } else {
+        0.0
+    };
+
+    Ok(ConversationAnalytics {
+        messages_per_day,
+        messages_by_role,
+        active_conversations,
+        avg_messages_per_conversation,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -582,7 +1320,7 @@ mod tests {
         );
     }
 
-    // Critical fix #1: Timestamp numeric validation
+    // Calendar validation now comes from chrono, not hand-rolled range checks.
     #[test]
     fn test_validate_timestamp_invalid_month_13() {
         let msg = MessageToSave {
@@ -593,10 +1331,7 @@ mod tests {
             tool_results: None,
             created_at: "2026-13-27T12:00:00Z".to_string(),
         };
-        assert_eq!(
-            msg.validate_timestamp().unwrap_err(),
-            "Invalid timestamp: month must be 1-12"
-        );
+        assert!(msg.validate_timestamp().is_err());
     }
 
     #[test]
@@ -609,10 +1344,7 @@ mod tests {
             tool_results: None,
             created_at: "2026-00-27T12:00:00Z".to_string(),
         };
-        assert_eq!(
-            msg.validate_timestamp().unwrap_err(),
-            "Invalid timestamp: month must be 1-12"
-        );
+        assert!(msg.validate_timestamp().is_err());
     }
 
     #[test]
@@ -625,10 +1357,21 @@ mod tests {
             tool_results: None,
             created_at: "2026-01-32T12:00:00Z".to_string(),
         };
-        assert_eq!(
-            msg.validate_timestamp().unwrap_err(),
-            "Invalid timestamp: day must be 1-31"
-        );
+        assert!(msg.validate_timestamp().is_err());
+    }
+
+    #[test]
+    fn test_validate_timestamp_invalid_feb_31() {
+        // Hand-rolled validation used to accept this; chrono correctly rejects it.
+        let msg = MessageToSave {
+            id: "msg_123".to_string(),
+            role: "user".to_string(),
+            content: "test".to_string(),
+            tool_calls: None,
+            tool_results: None,
+            created_at: "2026-02-31T12:00:00Z".to_string(),
+        };
+        assert!(msg.validate_timestamp().is_err());
     }
 
     #[test]
@@ -641,10 +1384,7 @@ mod tests {
             tool_results: None,
             created_at: "2026-01-27T24:00:00Z".to_string(),
         };
-        assert_eq!(
-            msg.validate_timestamp().unwrap_err(),
-            "Invalid timestamp: hour must be 0-23"
-        );
+        assert!(msg.validate_timestamp().is_err());
     }
 
     #[test]
@@ -657,10 +1397,7 @@ mod tests {
             tool_results: None,
             created_at: "2026-01-27T12:60:00Z".to_string(),
         };
-        assert_eq!(
-            msg.validate_timestamp().unwrap_err(),
-            "Invalid timestamp: minute must be 0-59"
-        );
+        assert!(msg.validate_timestamp().is_err());
     }
 
     #[test]
@@ -673,10 +1410,7 @@ mod tests {
             tool_results: None,
             created_at: "2026-01-27T12:00:60Z".to_string(),
         };
-        assert_eq!(
-            msg.validate_timestamp().unwrap_err(),
-            "Invalid timestamp: second must be 0-59"
-        );
+        assert!(msg.validate_timestamp().is_err());
     }
 
     #[test]
@@ -689,10 +1423,7 @@ mod tests {
             tool_results: None,
             created_at: "2026-XX-27T12:00:00Z".to_string(),
         };
-        assert_eq!(
-            msg.validate_timestamp().unwrap_err(),
-            "Invalid timestamp: month must be numeric"
-        );
+        assert!(msg.validate_timestamp().is_err());
     }
 
     #[test]
@@ -711,7 +1442,7 @@ mod tests {
 
     #[test]
     fn test_validate_timestamp_with_milliseconds() {
-        // Timestamps with milliseconds should still pass (length > 19)
+        // Timestamps with milliseconds should still pass
         let msg = MessageToSave {
             id: "msg_123".to_string(),
             role: "user".to_string(),
@@ -722,4 +1453,115 @@ mod tests {
         };
         assert!(msg.validate_timestamp().is_ok());
     }
+
+    #[test]
+    fn test_normalized_created_at_preserves_offset() {
+        let msg = MessageToSave {
+            id: "msg_123".to_string(),
+            role: "user".to_string(),
+            content: "test".to_string(),
+            tool_calls: None,
+            tool_results: None,
+            created_at: "2026-01-27T12:00:00+05:30".to_string(),
+        };
+        let (utc, offset) = msg.normalized_created_at().unwrap();
+        assert_eq!(utc, "2026-01-27T06:30:00Z");
+        assert_eq!(offset, "+05:30");
+    }
+
+    // =========================================================================
+    // Database-backed round-trip tests (temp-file-backed DbState, real SQLite)
+    // =========================================================================
+
+    fn test_db() -> crate::db::DbState {
+        let path = std::env::temp_dir().join(format!("orion_test_conversation_{}.db", uuid::Uuid::new_v4()));
+        let db_state = crate::db::DbState::new(&path).expect("failed to open test database");
+        let mut conn = db_state.pool.get().expect("failed to check out test connection");
+        crate::db::migrations::run(&mut conn).expect("failed to run test migrations");
+        drop(conn);
+        db_state
+    }
+
+    #[test]
+    fn test_revise_message_preserves_original_created_at_and_ordering() {
+        let db = test_db();
+
+        ConversationTurnBuilder::new("conv_test_revise")
+            .message(MessageToSave {
+                id: "msg_1".to_string(),
+                role: "user".to_string(),
+                content: "original".to_string(),
+                tool_calls: None,
+                tool_results: None,
+                created_at: "2026-01-27T10:00:00Z".to_string(),
+            })
+            .save(&db)
+            .unwrap();
+
+        revise_message(
+            tauri::State::from(&db),
+            "conv_test_revise".to_string(),
+            "msg_1".to_string(),
+            "revised".to_string(),
+            "2026-01-27T12:00:00Z".to_string(),
+        )
+        .unwrap();
+
+        // The live version keeps the ORIGINAL created_at (not the edit
+        // time), so it doesn't get reordered out of its original spot.
+        let live = get_conversation_as_of(
+            tauri::State::from(&db),
+            "conv_test_revise".to_string(),
+            "2026-01-27T13:00:00Z".to_string(),
+        )
+        .unwrap();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].content, "revised");
+        assert_eq!(live[0].created_at, "2026-01-27T10:00:00Z");
+
+        // The pre-revision snapshot still sees the original content.
+        let historical = get_conversation_as_of(
+            tauri::State::from(&db),
+            "conv_test_revise".to_string(),
+            "2026-01-27T11:00:00Z".to_string(),
+        )
+        .unwrap();
+        assert_eq!(historical.len(), 1);
+        assert_eq!(historical[0].content, "original");
+    }
+
+    #[test]
+    fn test_export_import_round_trip_remaps_ids_on_collision() {
+        let db = test_db();
+
+        ConversationTurnBuilder::new("conv_test_export")
+            .message(MessageToSave {
+                id: "msg_1".to_string(),
+                role: "user".to_string(),
+                content: "hello".to_string(),
+                tool_calls: None,
+                tool_results: None,
+                created_at: "2026-01-27T10:00:00Z".to_string(),
+            })
+            .save(&db)
+            .unwrap();
+
+        let document =
+            export_conversation(tauri::State::from(&db), "conv_test_export".to_string()).unwrap();
+
+        // Importing a transcript alongside its still-present origin must
+        // remap both the conversation id and the message row ids, or this
+        // hits a UNIQUE violation on `messages.id`.
+        let imported_id = import_conversation(tauri::State::from(&db), document).unwrap();
+        assert_ne!(imported_id, "conv_test_export");
+
+        let imported_messages = get_conversation_as_of(
+            tauri::State::from(&db),
+            imported_id,
+            "2026-01-27T11:00:00Z".to_string(),
+        )
+        .unwrap();
+        assert_eq!(imported_messages.len(), 1);
+        assert_eq!(imported_messages[0].content, "hello");
+    }
 }
@@ -3,8 +3,19 @@
 //! Tauri IPC commands for database operations.
 //! Story 3.1: SQLite Database Initialization
 
+use crate::db::DbState;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Manager};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use tauri::State;
+
+/// Magic bytes identifying an `.orion.bak` file.
+const BACKUP_MAGIC: &[u8; 4] = b"OBAK";
+/// Backup container format version (the envelope, not the schema inside it).
+const BACKUP_FORMAT_VERSION: u8 = 1;
 
 /// Database health status
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,75 +26,440 @@ pub struct DbHealthStatus {
     pub foreign_keys_enabled: bool,
     pub db_path: String,
     pub db_size_bytes: u64,
+    /// True if the database was found corrupted on open and rebuilt fresh.
+    pub recovered: bool,
+    /// Path the corrupted file was quarantined to, if `recovered` is true.
+    pub quarantined_path: Option<String>,
 }
 
 /// Check database health and configuration
+///
+/// Reads the live PRAGMA values off a connection checked out of the
+/// `DbState` pool rather than assuming the state TypeScript last set, so Rust
+/// and TS can't disagree about what's actually on disk.
 #[tauri::command]
-pub async fn db_health_check(app: AppHandle) -> Result<DbHealthStatus, String> {
-    // Get app data directory
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
-
-    let db_path = app_data_dir.join(crate::db::config::DB_FILENAME);
-
-    // Check if database exists
-    if !db_path.exists() {
+pub fn db_health_check(db: State<'_, DbState>) -> Result<DbHealthStatus, String> {
+    if !db.db_path.exists() {
         return Ok(DbHealthStatus {
             initialized: false,
             journal_mode: String::new(),
             foreign_keys_enabled: false,
-            db_path: db_path.to_string_lossy().to_string(),
+            db_path: db.db_path.to_string_lossy().to_string(),
             db_size_bytes: 0,
+            recovered: db.recovered,
+            quarantined_path: db
+                .quarantined_path
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
         });
     }
 
-    // Get file size
-    let db_size_bytes = std::fs::metadata(&db_path)
-        .map(|m| m.len())
-        .unwrap_or(0);
+    let conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let journal_mode: String = conn
+        .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to read journal_mode: {}", e))?;
+
+    let foreign_keys_enabled: bool = conn
+        .query_row("PRAGMA foreign_keys", [], |row| row.get::<_, i64>(0))
+        .map_err(|e| format!("Failed to read foreign_keys: {}", e))?
+        != 0;
+
+    let page_count: u64 = conn
+        .query_row("PRAGMA page_count", [], |row| row.get::<_, i64>(0))
+        .map_err(|e| format!("Failed to read page_count: {}", e))? as u64;
+
+    let page_size: u64 = conn
+        .query_row("PRAGMA page_size", [], |row| row.get::<_, i64>(0))
+        .map_err(|e| format!("Failed to read page_size: {}", e))? as u64;
 
-    // NOTE: journal_mode and foreign_keys values here are "expected" not "verified"
-    // Actual verification happens in TypeScript via initializeDatabase() which
-    // queries PRAGMA values after connection. This command only checks file existence.
-    // See Pre-Mortem mitigation: TypeScript is source of truth for PRAGMA verification.
     Ok(DbHealthStatus {
         initialized: true,
-        journal_mode: "wal".to_string(), // Expected value - verified by TypeScript
-        foreign_keys_enabled: true,
-        db_path: db_path.to_string_lossy().to_string(),
-        db_size_bytes,
+        journal_mode,
+        foreign_keys_enabled,
+        db_path: db.db_path.to_string_lossy().to_string(),
+        db_size_bytes: page_count * page_size,
+        recovered: db.recovered,
+        quarantined_path: db
+            .quarantined_path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string()),
     })
 }
 
-/// Get database path
+/// Result of applying pending migrations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationStatus {
+    pub before_version: u32,
+    pub after_version: u32,
+    pub applied: Vec<String>,
+}
+
+/// Apply any pending schema migrations
+///
+/// Runs every embedded migration newer than the version already recorded
+/// in `_orion_migrations`, inside a single transaction.
+#[tauri::command]
+pub fn db_migrate(db: State<'_, DbState>) -> Result<MigrationStatus, String> {
+    let mut conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let report = crate::db::migrations::run(&mut conn)?;
+
+    println!(
+        "[db] Migrated schema {} -> {} ({} applied)",
+        report.before_version,
+        report.after_version,
+        report.applied.len()
+    );
+
+    Ok(MigrationStatus {
+        before_version: report.before_version,
+        after_version: report.after_version,
+        applied: report.applied,
+    })
+}
+
+/// Keys the legacy store used for its own bookkeeping; these are never
+/// imported as user data.
+const RESERVED_LEGACY_KEYS: &[&str] = &["_internal", "_meta", "_schema_version", "__proto__"];
+
+fn is_reserved_legacy_key(key: &str) -> bool {
+    key.starts_with("__") || RESERVED_LEGACY_KEYS.contains(&key)
+}
+
+/// Summary of a `db_migrate_from_legacy` run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyImportSummary {
+    pub migrated: u32,
+    pub skipped_keys: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// One-shot import of a legacy JSON/key-value store into `kv_store`
+///
+/// Reads `source_path` as a flat JSON object and inserts every
+/// non-reserved key inside a single transaction, deliberately not
+/// enforcing the size quotas newer writes do — this is a best-effort
+/// migration of whatever the old build persisted. Records whose insert
+/// fails are collected in `errors` rather than aborting the whole import;
+/// a source that is not one valid JSON object is rejected outright. The source
+/// file is only renamed aside (to `<source_path>.imported`) once the
+/// transaction commits, so a crash mid-import can simply be retried.
 #[tauri::command]
-pub fn db_get_path(app: AppHandle) -> Result<String, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+pub fn db_migrate_from_legacy(
+    db: State<'_, DbState>,
+    source_path: String,
+) -> Result<LegacyImportSummary, String> {
+    let raw = std::fs::read_to_string(&source_path)
+        .map_err(|e| format!("Failed to read legacy store: {}", e))?;
+
+    let document: serde_json::Value = serde_json::from_str(&raw)
+        .map_err(|e| format!("Legacy store is not valid JSON: {}", e))?;
+
+    let object = document
+        .as_object()
+        .ok_or_else(|| "Legacy store must be a flat JSON object".to_string())?;
+
+    let mut conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start import transaction: {}", e))?;
+
+    let mut migrated = 0u32;
+    let mut skipped_keys = Vec::new();
+    let mut errors = Vec::new();
+
+    for (key, value) in object {
+        if is_reserved_legacy_key(key) {
+            skipped_keys.push(key.clone());
+            continue;
+        }
+
+        let value_text = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+
+        match tx.execute(
+            "INSERT INTO kv_store (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            rusqlite::params![key, value_text],
+        ) {
+            Ok(_) => migrated += 1,
+            Err(e) => errors.push(format!("{}: {}", key, e)),
+        }
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit legacy import: {}", e))?;
+    drop(conn);
+
+    // Only move the source aside once the import has actually landed, so a
+    // crash before this point leaves it untouched for a clean retry.
+    let imported_path = format!("{}.imported", source_path);
+    if let Err(e) = std::fs::rename(&source_path, &imported_path) {
+        eprintln!(
+            "[db] Legacy import committed but failed to rename source {}: {}",
+            source_path, e
+        );
+    }
+
+    println!(
+        "[db] Imported {} legacy keys ({} skipped, {} errors)",
+        migrated,
+        skipped_keys.len(),
+        errors.len()
+    );
 
-    let db_path = app_data_dir.join(crate::db::config::DB_FILENAME);
+    Ok(LegacyImportSummary {
+        migrated,
+        skipped_keys,
+        errors,
+    })
+}
 
-    Ok(db_path.to_string_lossy().to_string())
+fn current_schema_version(conn: &rusqlite::Connection) -> Result<u32, String> {
+    conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM _orion_migrations",
+        [],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|v| v as u32)
+    .or(Ok(0))
 }
 
-/// Ensure app data directory exists
+/// Create a compressed, integrity-checked backup of the database
+///
+/// Uses SQLite's online backup API so it can run against the live,
+/// WAL-mode connection, then gzips the resulting snapshot behind a small
+/// header recording the schema version and a SHA-256 of the raw snapshot.
 #[tauri::command]
-pub fn db_ensure_dir(app: AppHandle) -> Result<String, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
-
-    if !app_data_dir.exists() {
-        std::fs::create_dir_all(&app_data_dir)
+pub fn db_backup(db: State<'_, DbState>, dest_path: String) -> Result<String, String> {
+    let conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    let snapshot_path = format!("{}.snapshot-tmp", dest_path);
+    let _ = std::fs::remove_file(&snapshot_path);
+
+    {
+        let mut snapshot = rusqlite::Connection::open(&snapshot_path)
+            .map_err(|e| format!("Failed to create backup snapshot: {}", e))?;
+
+        let backup = rusqlite::backup::Backup::new(&conn, &mut snapshot)
+            .map_err(|e| format!("Failed to start online backup: {}", e))?;
+        backup
+            .run_to_completion(100, std::time::Duration::from_millis(10), None)
+            .map_err(|e| format!("Online backup failed: {}", e))?;
+    }
+
+    let schema_version = current_schema_version(&conn)?;
+    drop(conn);
+
+    let raw = std::fs::read(&snapshot_path)
+        .map_err(|e| format!("Failed to read backup snapshot: {}", e))?;
+    let _ = std::fs::remove_file(&snapshot_path);
+
+    let hash: [u8; 32] = Sha256::digest(&raw).into();
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&raw)
+        .map_err(|e| format!("Failed to compress backup: {}", e))?;
+    let compressed = encoder
+        .finish()
+        .map_err(|e| format!("Failed to finalize backup: {}", e))?;
+
+    let mut file = std::fs::File::create(&dest_path)
+        .map_err(|e| format!("Failed to create backup file: {}", e))?;
+    file.write_all(BACKUP_MAGIC)
+        .and_then(|_| file.write_all(&[BACKUP_FORMAT_VERSION]))
+        .and_then(|_| file.write_all(&schema_version.to_le_bytes()))
+        .and_then(|_| file.write_all(&hash))
+        .and_then(|_| file.write_all(&compressed))
+        .map_err(|e| format!("Failed to write backup file: {}", e))?;
+
+    println!(
+        "[db] Backed up database (schema v{}) to {}",
+        schema_version, dest_path
+    );
+
+    Ok(dest_path)
+}
+
+/// Restore the database from a `.orion.bak` file produced by `db_backup`
+///
+/// Verifies the stored hash and schema version, decompresses to a temp
+/// file, runs `quick_check` on it, and only then uses the online backup
+/// API to copy it into the live database file (the same mechanism
+/// `db_backup` uses in reverse). That rewrites the pages of the file
+/// every pooled connection already has open, rather than swapping the
+/// file out from under them.
+///
+/// Before that copy runs, the current live database is snapshotted to a
+/// sibling file so a restore that fails partway through (leaving the live
+/// file's pages half-rewritten) can be rolled back instead of bricking it.
+#[tauri::command]
+pub fn db_restore(db: State<'_, DbState>, src_path: String) -> Result<(), String> {
+    let bytes =
+        std::fs::read(&src_path).map_err(|e| format!("Failed to read backup file: {}", e))?;
+
+    if bytes.len() < 4 + 1 + 4 + 32 {
+        return Err("Backup file is truncated or not an .orion.bak file".to_string());
+    }
+    if &bytes[0..4] != BACKUP_MAGIC {
+        return Err("Not a valid .orion.bak file".to_string());
+    }
+    let format_version = bytes[4];
+    if format_version != BACKUP_FORMAT_VERSION {
+        return Err(format!(
+            "Unsupported backup format version: {}",
+            format_version
+        ));
+    }
+    let schema_version = u32::from_le_bytes(bytes[5..9].try_into().unwrap());
+    let expected_hash = &bytes[9..41];
+    let compressed = &bytes[41..];
+
+    let latest_known_version = crate::db::migrations::MIGRATIONS
+        .iter()
+        .map(|m| m.version)
+        .max()
+        .unwrap_or(0);
+    if schema_version > latest_known_version {
+        return Err(format!(
+            "Backup schema version {} is newer than this build supports (v{})",
+            schema_version, latest_known_version
+        ));
+    }
+
+    let mut decoder = GzDecoder::new(compressed);
+    let mut raw = Vec::new();
+    decoder
+        .read_to_end(&mut raw)
+        .map_err(|e| format!("Failed to decompress backup: {}", e))?;
+
+    let actual_hash = Sha256::digest(&raw);
+    if actual_hash.as_slice() != expected_hash {
+        return Err("Backup integrity check failed: hash mismatch".to_string());
+    }
+
+    let restore_tmp_path = db.db_path.with_extension("restore-tmp");
+    std::fs::write(&restore_tmp_path, &raw)
+        .map_err(|e| format!("Failed to stage restored database: {}", e))?;
+
+    let staged = rusqlite::Connection::open(&restore_tmp_path)
+        .map_err(|e| format!("Failed to open staged database: {}", e))?;
+    let check: String = staged
+        .query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to verify staged database: {}", e))?;
+    if check != "ok" {
+        let _ = std::fs::remove_file(&restore_tmp_path);
+        return Err(format!("Restored database failed integrity check: {}", check));
+    }
+
+    let mut conn = db
+        .pool
+        .get()
+        .map_err(|_| "Database temporarily unavailable".to_string())?;
+
+    // Snapshot the current live database before overwriting it. If the
+    // restore backup below fails partway through, the live DB's pages are
+    // left in a half-rewritten state - without this, that data would be
+    // unrecoverable. Kept as a plain SQLite file beside the live one
+    // (`orion.pre-restore-safety`) rather than in memory, since it needs to
+    // survive the online backup API writing into `conn` right after it's
+    // taken.
+    let safety_path = db.db_path.with_extension("pre-restore-safety");
+    let _ = std::fs::remove_file(&safety_path);
+    {
+        let mut safety = rusqlite::Connection::open(&safety_path)
+            .map_err(|e| format!("Failed to create pre-restore safety snapshot: {}", e))?;
+        let safety_backup = rusqlite::backup::Backup::new(&conn, &mut safety)
+            .map_err(|e| format!("Failed to start pre-restore safety snapshot: {}", e))?;
+        safety_backup
+            .run_to_completion(100, std::time::Duration::from_millis(10), None)
+            .map_err(|e| format!("Failed to snapshot current database before restore: {}", e))?;
+    }
+
+    let backup = rusqlite::backup::Backup::new(&staged, &mut conn)
+        .map_err(|e| format!("Failed to start restore backup: {}", e))?;
+
+    if let Err(e) = backup.run_to_completion(100, std::time::Duration::from_millis(10), None) {
+        // The live DB may now be partially rewritten. Roll it back from
+        // the safety snapshot taken above rather than leaving it corrupt.
+        let rollback_failed = (|| -> Result<(), String> {
+            let safety = rusqlite::Connection::open(&safety_path)
+                .map_err(|e| format!("Failed to reopen pre-restore safety snapshot: {}", e))?;
+            let rollback = rusqlite::backup::Backup::new(&safety, &mut conn)
+                .map_err(|e| format!("Failed to start rollback: {}", e))?;
+            rollback
+                .run_to_completion(100, std::time::Duration::from_millis(10), None)
+                .map_err(|e| format!("Rollback failed: {}", e))
+        })()
+        .err();
+
+        return Err(match rollback_failed {
+            None => {
+                let _ = std::fs::remove_file(&safety_path);
+                format!(
+                    "Restore failed and the database was rolled back to its pre-restore state: {}",
+                    e
+                )
+            }
+            Some(rollback_err) => format!(
+                "Restore failed ({}) and automatic rollback also failed ({}) - the pre-restore snapshot is preserved at {}",
+                e,
+                rollback_err,
+                safety_path.display()
+            ),
+        });
+    }
+
+    drop(staged);
+    let _ = std::fs::remove_file(&restore_tmp_path);
+    let _ = std::fs::remove_file(&safety_path);
+
+    println!(
+        "[db] Restored database (schema v{}) from {}",
+        schema_version, src_path
+    );
+
+    Ok(())
+}
+
+/// Get database path
+#[tauri::command]
+pub fn db_get_path(db: State<'_, DbState>) -> Result<String, String> {
+    Ok(db.db_path.to_string_lossy().to_string())
+}
+
+/// Ensure the directory holding the database exists
+#[tauri::command]
+pub fn db_ensure_dir(db: State<'_, DbState>) -> Result<String, String> {
+    let dir = db
+        .db_path
+        .parent()
+        .ok_or_else(|| "Database path has no parent directory".to_string())?;
+
+    if !dir.exists() {
+        std::fs::create_dir_all(dir)
             .map_err(|e| format!("Failed to create app data dir: {}", e))?;
     }
 
-    Ok(app_data_dir.to_string_lossy().to_string())
+    Ok(dir.to_string_lossy().to_string())
 }
 
 #[cfg(test)]
@@ -98,10 +474,27 @@ mod tests {
             foreign_keys_enabled: true,
             db_path: "/test/path/orion.db".to_string(),
             db_size_bytes: 4096,
+            recovered: false,
+            quarantined_path: None,
         };
 
         let json = serde_json::to_string(&status).unwrap();
         assert!(json.contains("journalMode"));
         assert!(json.contains("foreignKeysEnabled"));
     }
+
+    #[test]
+    fn test_is_reserved_legacy_key_matches_documented_keys() {
+        assert!(is_reserved_legacy_key("_internal"));
+        assert!(is_reserved_legacy_key("_meta"));
+        assert!(is_reserved_legacy_key("_schema_version"));
+        assert!(is_reserved_legacy_key("__proto__"));
+        assert!(is_reserved_legacy_key("__anything_double_underscored"));
+    }
+
+    #[test]
+    fn test_is_reserved_legacy_key_keeps_legitimate_underscore_prefixed_keys() {
+        assert!(!is_reserved_legacy_key("_draft"));
+        assert!(!is_reserved_legacy_key("_lastProject"));
+    }
 }
@@ -0,0 +1,92 @@
+//! Server-side syntax highlighting for fenced code blocks in message content
+//!
+//! Moves highlighting off the UI thread: the frontend used to tokenize
+//! every code block on every render, so `load_session_highlighted` does it
+//! once in Rust via `syntect` and hands back ready-to-style HTML spans.
+
+use std::sync::OnceLock;
+use syntect::html::{ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+
+static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+
+fn syntax_set() -> &'static SyntaxSet {
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// Replace every fenced (``` ... ```) code block in `content` with a
+/// `<pre><code>` element holding `syntect`-generated HTML (`<span
+/// class="...">` tokens via `ClassedHTMLGenerator`), keyed off the
+/// language tag on the fence's info string. Prose outside code fences is
+/// left untouched. Unknown or missing language tags fall back to
+/// plain-text tokenization, so every block is still wrapped consistently
+/// for the frontend's theme CSS to style.
+///
+/// The markdown fence lines themselves are dropped rather than re-emitted
+/// around the generated HTML: a markdown renderer treats whatever sits
+/// between ``` ``` ``` as literal text and HTML-escapes it, which would
+/// print the `<span>` tags instead of applying them.
+pub fn highlight_code_blocks(content: &str) -> String {
+    let syntax_set = syntax_set();
+    let mut output = String::with_capacity(content.len());
+    let mut lines = content.split_inclusive('\n').peekable();
+
+    while let Some(line) = lines.next() {
+        let Some(lang) = line.trim_start().strip_prefix("```") else {
+            output.push_str(line);
+            continue;
+        };
+
+        let lang = lang.trim();
+        let syntax = if lang.is_empty() {
+            syntax_set.find_syntax_plain_text()
+        } else {
+            syntax_set
+                .find_syntax_by_token(lang)
+                .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
+        };
+
+        let mut code_lines = Vec::new();
+        for code_line in lines.by_ref() {
+            if code_line.trim_start().starts_with("```") {
+                break;
+            }
+            code_lines.push(code_line);
+        }
+
+        let mut generator =
+            ClassedHTMLGenerator::new_with_class_style(syntax, syntax_set, ClassStyle::Spaced);
+        for code_line in &code_lines {
+            let _ = generator
+                .parse_html_for_line_which_includes_newline(&format!(
+                    "{}\n",
+                    code_line.trim_end_matches(['\r', '\n'])
+                ));
+        }
+
+        output.push_str(&format!(
+            "<pre class=\"highlight\"><code class=\"language-{}\">",
+            sanitize_lang_class(lang)
+        ));
+        output.push_str(&generator.finalize());
+        output.push_str("</code></pre>\n");
+    }
+
+    output
+}
+
+/// Restrict a fence's language tag to characters safe to drop straight
+/// into an HTML class attribute, so an unusual info string can't break out
+/// of it. Falls back to "text" for an empty or fully-stripped tag.
+fn sanitize_lang_class(lang: &str) -> String {
+    let cleaned: String = lang
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '+' || *c == '_')
+        .collect();
+
+    if cleaned.is_empty() {
+        "text".to_string()
+    } else {
+        cleaned
+    }
+}
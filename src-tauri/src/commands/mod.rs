@@ -1,6 +1,7 @@
 mod hello;
 mod chat;
 mod conversation;
+mod highlight;
 mod session;
 mod para;
 pub mod events;
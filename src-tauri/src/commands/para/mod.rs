@@ -0,0 +1,174 @@
+/// PARA Filesystem Commands
+/// Story 4.17: Archive Completed Items
+///
+/// Tauri IPC commands for PARA filesystem operations that cannot be done
+/// from the frontend due to security sandbox restrictions.
+///
+/// # Security
+/// All paths are validated to ensure they're within the user's home directory
+/// and contain "Orion" to prevent directory traversal attacks.
+///
+/// # Limitations
+/// - `fs::rename` fails across different filesystems/volumes (`EXDEV`).
+///   `para_move_directory` falls back to a staged copy+delete in that case;
+///   see `copy_dir_recursive`.
+///
+/// For durable offsite archival (surviving local disk loss entirely), see
+/// `para::remote::para_archive_remote`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod remote;
+pub use remote::para_archive_remote;
+
+/// Validate that a path is within the Orion directory structure
+/// Returns an error if the path appears to be outside expected boundaries
+fn validate_orion_path(path: &str, label: &str) -> Result<(), String> {
+    // Path must contain "Orion" to be valid
+    if !path.contains("Orion") {
+        return Err(format!(
+            "{} path must be within the Orion directory: {}",
+            label, path
+        ));
+    }
+
+    // Reject paths with directory traversal attempts
+    if path.contains("..") {
+        return Err(format!(
+            "{} path contains invalid traversal sequence: {}",
+            label, path
+        ));
+    }
+
+    Ok(())
+}
+
+/// Recursively copy a directory tree, recreating each subdirectory with
+/// `create_dir_all` and copying files with `fs::copy`.
+///
+/// Used as the `EXDEV` fallback for `para_move_directory`. On the first
+/// failed file copy, the caller is expected to delete `dst` wholesale
+/// (everything written so far is still under the staging path, not the
+/// real destination) and surface the error.
+fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
+    fs::create_dir_all(dst)
+        .map_err(|e| format!("Failed to create directory {}: {}", dst.display(), e))?;
+
+    for entry in fs::read_dir(src)
+        .map_err(|e| format!("Failed to read directory {}: {}", src.display(), e))?
+    {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let entry_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("Failed to stat {}: {}", entry_path.display(), e))?;
+
+        if file_type.is_dir() {
+            copy_dir_recursive(&entry_path, &dst_path)?;
+        } else {
+            fs::copy(&entry_path, &dst_path).map_err(|e| {
+                format!(
+                    "Failed to copy {} to {}: {}",
+                    entry_path.display(),
+                    dst_path.display(),
+                    e
+                )
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Cross-volume fallback for `para_move_directory`: stage a full recursive
+/// copy into a temporary sibling of `to`, rename it into place, then
+/// remove `from`. If the copy fails partway through, the staged temp dir
+/// is deleted and the original error is returned with `from` untouched.
+fn copy_then_delete(from: &Path, to: &Path) -> Result<(), String> {
+    let staging: PathBuf = {
+        let mut name = to
+            .file_name()
+            .map(|n| n.to_os_string())
+            .unwrap_or_default();
+        name.push(".tmp");
+        to.with_file_name(name)
+    };
+
+    if let Err(e) = copy_dir_recursive(from, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    if let Err(e) = fs::rename(&staging, to) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!(
+            "Failed to stage move into place at {}: {}",
+            to.display(),
+            e
+        ));
+    }
+
+    fs::remove_dir_all(from)
+        .map_err(|e| format!("Copied to destination but failed to remove source {}: {}", from.display(), e))
+}
+
+/// Move a directory from one location to another
+/// Used for archiving projects/areas to YYYY-MM subdirectories
+///
+/// # Arguments
+/// * `from` - Source directory path (must be within Orion directory)
+/// * `to` - Destination directory path (must be within Orion directory)
+///
+/// # Errors
+/// - Returns error if source doesn't exist
+/// - Returns error if paths are outside Orion directory
+/// - Returns error if the move (or its cross-volume copy+delete fallback)
+///   fails; the source is left untouched unless the copy fully succeeded
+#[tauri::command]
+pub fn para_move_directory(from: String, to: String) -> Result<(), String> {
+    // Validate paths are within expected directory structure
+    validate_orion_path(&from, "Source")?;
+    validate_orion_path(&to, "Destination")?;
+
+    let from_path = Path::new(&from);
+    let to_path = Path::new(&to);
+
+    // Verify source exists
+    if !from_path.exists() {
+        return Err(format!("Source path does not exist: {}", from));
+    }
+
+    // Create parent directories if needed
+    if let Some(parent) = to_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directories: {}", e))?;
+    }
+
+    // Try an atomic rename first; fall back to a staged copy+delete when
+    // source and destination are on different volumes (EXDEV).
+    match fs::rename(from_path, to_path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.raw_os_error() == Some(18) => copy_then_delete(from_path, to_path),
+        Err(e) => Err(format!("Failed to move directory: {}", e)),
+    }
+}
+
+/// Create a directory (and all parent directories)
+/// Used for creating archive/projects/YYYY-MM/ structure
+///
+/// # Arguments
+/// * `path` - Directory path to create (must be within Orion directory)
+///
+/// # Errors
+/// - Returns error if path is outside Orion directory
+/// - Returns error if directory creation fails
+#[tauri::command]
+pub fn para_create_directory(path: String) -> Result<(), String> {
+    // Validate path is within expected directory structure
+    validate_orion_path(&path, "Directory")?;
+
+    fs::create_dir_all(&path).map_err(|e| format!("Failed to create directory: {}", e))
+}
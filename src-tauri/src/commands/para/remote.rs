@@ -0,0 +1,114 @@
+//! S3-compatible remote archive backend for PARA items
+//!
+//! Gives an archived project a durable offsite copy alongside the local
+//! YYYY-MM move `para_move_directory` performs, so a dead machine doesn't
+//! also mean a lost archive.
+
+use super::validate_orion_path;
+use s3::creds::Credentials;
+use s3::{Bucket, Region};
+use serde::Deserialize;
+use std::fs::File;
+use std::path::Path;
+
+/// User-supplied connection details for the S3-compatible endpoint.
+/// Works against self-hosted object stores (MinIO, etc.), not just AWS,
+/// via a custom endpoint URL and path-style addressing.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteArchiveConfig {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+/// Package `source` into a tar archive and upload it to the configured
+/// S3-compatible bucket under `projects/YYYY-MM/<name>.tar`, returning the
+/// object key on success.
+///
+/// The tar is built to a temporary file on disk rather than buffered in
+/// memory, then streamed to the bucket from that file, so archiving a
+/// large project tree doesn't hold its entire contents in RAM at once.
+#[tauri::command]
+pub async fn para_archive_remote(
+    source: String,
+    config: RemoteArchiveConfig,
+) -> Result<String, String> {
+    validate_orion_path(&source, "Source")?;
+
+    let source_path = Path::new(&source);
+    if !source_path.exists() {
+        return Err(format!("Source path does not exist: {}", source));
+    }
+
+    let name = source_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| "Source path has no directory name".to_string())?;
+
+    let now = chrono::Utc::now();
+    let object_key = format!("projects/{}/{}.tar", now.format("%Y-%m"), name);
+
+    let tar_path =
+        std::env::temp_dir().join(format!("orion-archive-{}.tar", uuid::Uuid::new_v4()));
+    {
+        let tar_file = File::create(&tar_path)
+            .map_err(|e| format!("Failed to create archive staging file: {}", e))?;
+        let mut builder = tar::Builder::new(tar_file);
+        builder
+            .append_dir_all(name, source_path)
+            .map_err(|e| format!("Failed to package archive: {}", e))?;
+        builder
+            .finish()
+            .map_err(|e| format!("Failed to finalize archive: {}", e))?;
+    }
+
+    let upload_result = upload_tar(&tar_path, &object_key, &config).await;
+    let _ = std::fs::remove_file(&tar_path);
+    upload_result?;
+
+    println!(
+        "[para] Archived {} to remote object {}",
+        source, object_key
+    );
+
+    Ok(object_key)
+}
+
+/// Stream the staged tar file at `tar_path` up to the configured bucket
+/// under `object_key`, without reading it into memory all at once.
+async fn upload_tar(
+    tar_path: &Path,
+    object_key: &str,
+    config: &RemoteArchiveConfig,
+) -> Result<(), String> {
+    let region = Region::Custom {
+        region: config.region.clone(),
+        endpoint: config.endpoint.clone(),
+    };
+    let credentials = Credentials::new(
+        Some(&config.access_key),
+        Some(&config.secret_key),
+        None,
+        None,
+        None,
+    )
+    .map_err(|e| format!("Invalid S3 credentials: {}", e))?;
+
+    let bucket = Bucket::new(&config.bucket, region, credentials)
+        .map_err(|e| format!("Failed to configure S3 bucket: {}", e))?
+        .with_path_style();
+
+    let mut tar_file = tokio::fs::File::open(tar_path)
+        .await
+        .map_err(|e| format!("Failed to reopen archive for upload: {}", e))?;
+
+    bucket
+        .put_object_stream(&mut tar_file, object_key)
+        .await
+        .map_err(|e| format!("Failed to upload archive to S3: {}", e))?;
+
+    Ok(())
+}
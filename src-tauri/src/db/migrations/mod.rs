@@ -0,0 +1,149 @@
+//! Forward-only SQL migrations.
+//!
+//! Replaces the single `INIT_SQL` blob with an ordered, versioned set of
+//! scripts embedded at compile time. Applied versions and a checksum of
+//! their contents are tracked in `_orion_migrations`, so history can't be
+//! silently edited out from under an already-upgraded database.
+
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+
+/// One embedded migration script.
+pub struct Migration {
+    pub version: u32,
+    pub file_name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Ordered list of embedded migrations. Append new entries; never edit or
+/// remove an already-released one.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        file_name: "V1__initial.sql",
+        sql: include_str!("V1__initial.sql"),
+    },
+    Migration {
+        version: 2,
+        file_name: "V2__kv_store.sql",
+        sql: include_str!("V2__kv_store.sql"),
+    },
+    Migration {
+        version: 3,
+        file_name: "V3__message_bitemporal.sql",
+        sql: include_str!("V3__message_bitemporal.sql"),
+    },
+    Migration {
+        version: 4,
+        file_name: "V4__timezone_normalization.sql",
+        sql: include_str!("V4__timezone_normalization.sql"),
+    },
+    Migration {
+        version: 5,
+        file_name: "V5__messages_fts.sql",
+        sql: include_str!("V5__messages_fts.sql"),
+    },
+];
+
+/// Result of a `db_migrate` run.
+pub struct MigrationReport {
+    pub before_version: u32,
+    pub after_version: u32,
+    pub applied: Vec<String>,
+}
+
+fn checksum(sql: &str) -> String {
+    let digest = Sha256::digest(sql.as_bytes());
+    format!("{:x}", digest)
+}
+
+fn ensure_migrations_table(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS _orion_migrations (
+            version INTEGER PRIMARY KEY,
+            file_name TEXT NOT NULL,
+            checksum TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )
+        "#,
+        [],
+    )?;
+    Ok(())
+}
+
+/// Apply every migration with a version greater than the highest applied
+/// one, inside a single transaction. Refuses to run (and rolls back) if a
+/// previously-applied script's checksum no longer matches what's on disk.
+pub fn run(conn: &mut Connection) -> Result<MigrationReport, String> {
+    ensure_migrations_table(conn).map_err(|e| format!("Failed to create migrations table: {}", e))?;
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start migration transaction: {}", e))?;
+
+    let before_version: u32 = tx
+        .query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM _orion_migrations",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .map_err(|e| format!("Failed to read current schema version: {}", e))? as u32;
+
+    // Guard against edited history: every migration at or below the
+    // current version must still match its recorded checksum.
+    for migration in MIGRATIONS.iter().filter(|m| m.version <= before_version) {
+        let recorded: Option<String> = tx
+            .query_row(
+                "SELECT checksum FROM _orion_migrations WHERE version = ?1",
+                [migration.version],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to read migration record: {}", e))
+            .ok();
+
+        if let Some(recorded_checksum) = recorded {
+            if recorded_checksum != checksum(migration.sql) {
+                return Err(format!(
+                    "Migration {} ({}) has been modified since it was applied",
+                    migration.version, migration.file_name
+                ));
+            }
+        }
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version > before_version) {
+        tx.execute_batch(migration.sql)
+            .map_err(|e| format!("Migration {} failed: {}", migration.version, e))?;
+
+        tx.execute(
+            "INSERT INTO _orion_migrations (version, file_name, checksum, applied_at) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![
+                migration.version,
+                migration.file_name,
+                checksum(migration.sql),
+                chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            ],
+        )
+        .map_err(|e| format!("Failed to record migration {}: {}", migration.version, e))?;
+
+        applied.push(migration.file_name.to_string());
+    }
+
+    let after_version = MIGRATIONS
+        .iter()
+        .map(|m| m.version)
+        .max()
+        .unwrap_or(before_version)
+        .max(before_version);
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit migrations: {}", e))?;
+
+    Ok(MigrationReport {
+        before_version,
+        after_version,
+        applied,
+    })
+}
@@ -4,8 +4,13 @@
 //!
 //! Handles database initialization, migrations, and health checks.
 
-/// SQL initialization script (compiled into binary)
-pub const INIT_SQL: &str = include_str!("init.sql");
+use r2d2::{CustomizeConnection, Pool};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub mod migrations;
 
 /// Database configuration constants
 pub mod config {
@@ -14,4 +19,131 @@ pub mod config {
 
     /// Expected WAL mode after init
     pub const EXPECTED_JOURNAL_MODE: &str = "wal";
+
+    /// Max number of pooled connections kept open concurrently. Read-heavy
+    /// commands (session list, search) can run across several of these at
+    /// once instead of queueing behind a single shared connection.
+    pub const POOL_SIZE: u32 = 8;
+
+    /// How long a checkout waits for a busy connection before giving up.
+    pub const BUSY_TIMEOUT_MS: u64 = 5000;
+}
+
+/// Applies the same per-connection setup `configure_pragmas` used to do,
+/// but on every connection the pool opens - not just the first one.
+#[derive(Debug)]
+struct PragmaCustomizer;
+
+impl CustomizeConnection<Connection, rusqlite::Error> for PragmaCustomizer {
+    fn on_acquire(&self, conn: &mut Connection) -> Result<(), rusqlite::Error> {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "foreign_keys", true)?;
+        conn.busy_timeout(Duration::from_millis(config::BUSY_TIMEOUT_MS))?;
+        Ok(())
+    }
+}
+
+/// Managed pool of database connections, held in Tauri app state.
+///
+/// Commands take `State<'_, DbState>` and check out a pooled connection
+/// per call instead of recomputing the app data dir and reopening
+/// `orion.db` on every IPC call, or serializing every query (including
+/// read-only ones) behind a single shared connection.
+pub struct DbState {
+    pub pool: Pool<SqliteConnectionManager>,
+    pub db_path: PathBuf,
+    /// Set if `new()` found a corrupted database and rebuilt it from scratch.
+    pub recovered: bool,
+    /// Path the corrupted file (and its `-wal`/`-shm` sidecars) were moved to.
+    pub quarantined_path: Option<PathBuf>,
+}
+
+pub fn configure_pragmas(conn: &Connection) -> Result<(), String> {
+    conn.pragma_update(None, "journal_mode", "WAL")
+        .map_err(|e| format!("Failed to enable WAL mode: {}", e))?;
+    conn.pragma_update(None, "foreign_keys", true)
+        .map_err(|e| format!("Failed to enable foreign keys: {}", e))?;
+    Ok(())
+}
+
+/// Run `PRAGMA quick_check` and report whether the database is sound.
+fn quick_check_ok(conn: &Connection) -> Result<bool, String> {
+    let result: String = conn
+        .query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to run quick_check: {}", e))?;
+    Ok(result == "ok")
+}
+
+/// Move a corrupted database (plus its WAL/SHM sidecars, if present) aside
+/// to a timestamped `orion.corrupt.<ts>.db` so it can be inspected or
+/// recovered from later, then return the quarantine path.
+fn quarantine(db_path: &Path) -> Result<PathBuf, String> {
+    let ts = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+    let quarantined = db_path.with_file_name(format!("orion.corrupt.{}.db", ts));
+
+    std::fs::rename(db_path, &quarantined)
+        .map_err(|e| format!("Failed to quarantine corrupted database: {}", e))?;
+
+    for ext in ["-wal", "-shm"] {
+        let sidecar = PathBuf::from(format!("{}{}", db_path.to_string_lossy(), ext));
+        if sidecar.exists() {
+            let quarantined_sidecar =
+                PathBuf::from(format!("{}{}", quarantined.to_string_lossy(), ext));
+            let _ = std::fs::rename(&sidecar, &quarantined_sidecar);
+        }
+    }
+
+    Ok(quarantined)
+}
+
+impl DbState {
+    /// Open (or create) the database at `path`, configure it for
+    /// concurrent reads (WAL) and referential integrity, verify it isn't
+    /// corrupted, and build a connection pool against it.
+    ///
+    /// If `PRAGMA quick_check` reports corruption, the damaged file is
+    /// quarantined and a fresh database is rebuilt from the embedded
+    /// migrations rather than crash-looping on every subsequent launch.
+    /// The corruption check itself still runs against a single bare
+    /// connection, opened before the pool exists.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, String> {
+        let db_path = path.as_ref().to_path_buf();
+        let existed_before = db_path.exists();
+
+        let conn = Connection::open(&db_path)
+            .map_err(|e| format!("Failed to open database: {}", e))?;
+        configure_pragmas(&conn)?;
+
+        let (recovered, quarantined_path) = if existed_before && !quick_check_ok(&conn)? {
+            drop(conn);
+            let quarantined = quarantine(&db_path)?;
+
+            let fresh = Connection::open(&db_path)
+                .map_err(|e| format!("Failed to recreate database: {}", e))?;
+            configure_pragmas(&fresh)?;
+
+            let mut fresh = fresh;
+            migrations::run(&mut fresh)
+                .map_err(|e| format!("Failed to rebuild schema after recovery: {}", e))?;
+
+            (true, Some(quarantined))
+        } else {
+            drop(conn);
+            (false, None)
+        };
+
+        let manager = SqliteConnectionManager::file(&db_path);
+        let pool = Pool::builder()
+            .max_size(config::POOL_SIZE)
+            .connection_customizer(Box::new(PragmaCustomizer))
+            .build(manager)
+            .map_err(|e| format!("Failed to create connection pool: {}", e))?;
+
+        Ok(Self {
+            pool,
+            db_path,
+            recovered,
+            quarantined_path,
+        })
+    }
 }
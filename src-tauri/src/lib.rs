@@ -48,16 +48,40 @@ pub fn run() {
                 commands::audit::init_audit_logger(app_data_dir.clone());
 
                 // Initialize rusqlite database for atomic transactions (Story 3.7/3.8)
-                // NOTE: Schema is created by TypeScript via tauri-plugin-sql
-                // This only opens a connection for transactional writes
+                // Rust owns the schema: `db::migrations` applies every pending
+                // versioned script right here, so session_index/conversations/
+                // messages are guaranteed to exist before any command runs -
+                // no more hoping TypeScript created them first.
                 let db_path = app_data_dir.join(db::config::DB_FILENAME);
                 match DbState::new(db_path.to_str().unwrap_or("orion.db")) {
                     Ok(db_state) => {
+                        let mut conn = db_state
+                            .pool
+                            .get()
+                            .expect("failed to check out a connection to run startup migrations");
+                        let report = db::migrations::run(&mut conn)
+                            .expect("failed to apply database migrations");
+                        drop(conn);
+
+                        if report.applied.is_empty() {
+                            println!(
+                                "[DB] Schema already at v{}, no migrations to apply",
+                                report.after_version
+                            );
+                        } else {
+                            println!(
+                                "[DB] Migrated schema v{} -> v{} ({} applied)",
+                                report.before_version,
+                                report.after_version,
+                                report.applied.len()
+                            );
+                        }
+
                         app.manage(db_state);
-                        println!("[DB] rusqlite connection initialized for transactions");
+                        println!("[DB] rusqlite connection pool initialized for transactions");
                     }
                     Err(e) => {
-                        // Log but don't fail - DB might not exist yet (TypeScript creates it)
+                        // Log but don't fail - app data dir might not be writable yet
                         eprintln!("[DB] rusqlite init deferred: {}", e);
                     }
                 }
@@ -78,16 +102,30 @@ pub fn run() {
             commands::db_health_check,
             commands::db_get_path,
             commands::db_ensure_dir,
+            commands::db_migrate,
+            commands::db_migrate_from_legacy,
+            commands::db_backup,
+            commands::db_restore,
             commands::save_conversation_turn,
             commands::get_or_create_conversation,
+            commands::revise_message,
+            commands::get_conversation_as_of,
+            commands::set_conversation_timezone,
+            commands::search_messages,
+            commands::export_conversation,
+            commands::import_conversation,
+            commands::conversation_analytics,
             commands::update_sdk_session_id,
             commands::get_recent_sessions,
             commands::load_session,
+            commands::load_session_highlighted,
             commands::create_session,
+            commands::search_sessions,
             commands::get_todays_daily_session,
             // PARA commands
             commands::para_move_directory,
             commands::para_create_directory,
+            commands::para_archive_remote,
             // Phase 0: Active Request Management
             commands::get_active_conversations,
             commands::update_active_request,